@@ -0,0 +1,88 @@
+//! Doom-fire cellular automaton: a downscaled heat grid seeded at the
+//! bottom and propagated upward each tick, giving a flickering, drifting
+//! flame background.
+
+use crate::rng::Rng;
+use web_sys::CanvasRenderingContext2d;
+
+/// Pixels per heat cell.
+const CELL: f64 = 4.0;
+
+pub struct DoomFire {
+    cols: usize,
+    rows: usize,
+    heat: Vec<u8>,
+}
+
+impl DoomFire {
+    pub fn new(width: f64, height: f64) -> Self {
+        let cols = (width / CELL).ceil() as usize;
+        let rows = (height / CELL).ceil() as usize;
+        let mut heat = vec![0u8; cols * rows];
+        for x in 0..cols {
+            heat[(rows - 1) * cols + x] = 255;
+        }
+        Self { cols, rows, heat }
+    }
+
+    /// Propagates heat upward: each cell takes the cell below, subtracts a
+    /// small random decay, and either stays in its column or drifts one
+    /// column to the left.
+    pub fn tick(&mut self, rng: &mut Rng) {
+        for y in 0..self.rows - 1 {
+            for x in 0..self.cols {
+                let below = self.heat[(y + 1) * self.cols + x];
+                if below == 0 {
+                    self.heat[y * self.cols + x] = 0;
+                    continue;
+                }
+                let rand = rng.next_u32();
+                let decay = (rand & 3) as u8;
+                let dst_x = x.saturating_sub((rand & 1) as usize).min(self.cols - 1);
+                self.heat[y * self.cols + dst_x] = below.saturating_sub(decay);
+            }
+        }
+        for x in 0..self.cols {
+            self.heat[(self.rows - 1) * self.cols + x] = 255;
+        }
+    }
+
+    pub fn draw(&self, ctx: &CanvasRenderingContext2d) {
+        for y in 0..self.rows {
+            for x in 0..self.cols {
+                let heat = self.heat[y * self.cols + x];
+                if heat == 0 {
+                    continue;
+                }
+                ctx.set_fill_style_str(&palette(heat));
+                ctx.fill_rect(x as f64 * CELL, y as f64 * CELL, CELL, CELL);
+            }
+        }
+    }
+}
+
+/// Maps a heat value through a warm black -> red -> orange -> yellow ->
+/// white palette.
+fn palette(heat: u8) -> String {
+    const STOPS: [(f64, (u8, u8, u8)); 5] = [
+        (0.0, (0, 0, 0)),
+        (0.25, (150, 0, 0)),
+        (0.5, (255, 110, 0)),
+        (0.75, (255, 220, 60)),
+        (1.0, (255, 255, 255)),
+    ];
+    let t = heat as f64 / 255.0;
+    let (mut r, mut g, mut b) = (0.0, 0.0, 0.0);
+    for pair in STOPS.windows(2) {
+        let (t0, c0) = pair[0];
+        let (t1, c1) = pair[1];
+        if t >= t0 && t <= t1 {
+            let span = (t - t0) / (t1 - t0);
+            r = c0.0 as f64 + (c1.0 as f64 - c0.0 as f64) * span;
+            g = c0.1 as f64 + (c1.1 as f64 - c0.1 as f64) * span;
+            b = c0.2 as f64 + (c1.2 as f64 - c0.2 as f64) * span;
+            break;
+        }
+    }
+    format!("rgb({}, {}, {})", r as u8, g as u8, b as u8)
+}
@@ -1,13 +1,121 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 use wasm_bindgen::prelude::*;
-use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use web_sys::{console, CanvasRenderingContext2d, HtmlCanvasElement, KeyboardEvent};
+
+mod fire;
+mod nn;
+mod rng;
+mod steering;
+use fire::DoomFire;
+use nn::Network;
+use rng::Rng;
+use steering::{AttractorPreset, Steering};
 
 const NUM_WORMS: usize = 12;
 const WORM_SEGMENTS: usize = 20;
 const SEGMENT_RADIUS: f64 = 6.0;
 const SEGMENT_SPACING: f64 = 10.0;
 
+/// Sensor inputs, two hidden layers, four steering outputs.
+const NETWORK_CONFIG: [usize; 4] = [6, 7, 7, 4];
+const NUM_FOOD: usize = 30;
+/// Size of the player's own food pool, kept separate from `NUM_FOOD` so a
+/// human player racing AI worms to pellets can't starve their fitness.
+const NUM_PLAYER_FOOD: usize = 8;
+const ELITE_COUNT: usize = 3;
+const MUT_RATE: f64 = 0.05;
+/// Frames allowed per generation before it's forced to end.
+const FRAME_BUDGET: u32 = 1800;
+const MIN_SPEED: f64 = 0.6;
+const MAX_SPEED: f64 = 3.0;
+
+/// Chaikin corner-cutting passes applied to the ribbon render mode.
+const CHAIKIN_ITERATIONS: u32 = 3;
+
+/// Segments nearest the head that don't count as a self-collision, since
+/// the smoothly-interpolated neck sits almost on top of the head anyway.
+const NECK_GAP: usize = 6;
+const PLAYER_TURN_SPEED: f64 = 0.12;
+const PLAYER_SPEED: f64 = 2.0;
+const PLAYER_HUE: f64 = 200.0;
+/// How long the "game over" text stays up before the player respawns.
+const GAME_OVER_FLASH_FRAMES: u32 = 90;
+/// Frames after a respawn during which the player can't collide with
+/// anything, so a fresh spawn can't be instantly killed by a worm that
+/// happened to be near the center.
+const RESPAWN_GRACE_FRAMES: u32 = 60;
+
+/// A pellet a worm's brain is rewarded for reaching.
+struct Food {
+    x: f64,
+    y: f64,
+}
+
+/// How a worm's body is drawn; cycled at runtime with the `R` key.
+#[derive(Clone, Copy, PartialEq)]
+enum RenderMode {
+    /// The original row of discrete, tapering circles.
+    Circles,
+    /// `self.segments` treated as a control polygon, Chaikin-smoothed into
+    /// a centerline, then filled as one tapered ribbon.
+    ChaikinRibbon,
+    /// The centerline stroked once as a round-jointed gradient path.
+    GradientStroke,
+}
+
+impl RenderMode {
+    fn next(self) -> Self {
+        match self {
+            RenderMode::Circles => RenderMode::ChaikinRibbon,
+            RenderMode::ChaikinRibbon => RenderMode::GradientStroke,
+            RenderMode::GradientStroke => RenderMode::Circles,
+        }
+    }
+}
+
+/// Chaikin corner-cutting: replaces each edge `(Pi, Pi+1)` of `points` with
+/// the two points `Q = 0.75*Pi + 0.25*Pi+1` and `R = 0.25*Pi + 0.75*Pi+1`,
+/// repeated for `iterations` passes.
+fn chaikin_smooth(points: &[(f64, f64)], iterations: u32) -> Vec<(f64, f64)> {
+    let mut pts = points.to_vec();
+    for _ in 0..iterations {
+        if pts.len() < 3 {
+            break;
+        }
+        let mut next = Vec::with_capacity(pts.len() * 2);
+        for w in pts.windows(2) {
+            let (p0, p1) = (w[0], w[1]);
+            next.push((0.75 * p0.0 + 0.25 * p1.0, 0.75 * p0.1 + 0.25 * p1.1));
+            next.push((0.25 * p0.0 + 0.75 * p1.0, 0.25 * p0.1 + 0.75 * p1.1));
+        }
+        pts = next;
+    }
+    pts
+}
+
+/// Points tracing a small semicircular fan of `radius` around `center`,
+/// starting from `from` (on the circle) and sweeping through `bulge`
+/// (a unit vector) to the point diametrically opposite `from`; `from` itself
+/// is not included, the opposite point is the last one. Used to cap
+/// the ribbon render mode's head and tail with a rounded end instead of a
+/// flat cut.
+fn cap_fan(center: (f64, f64), from: (f64, f64), radius: f64, bulge: (f64, f64)) -> Vec<(f64, f64)> {
+    const STEPS: usize = 8;
+    let (cx, cy) = center;
+    let (ux, uy) = ((from.0 - cx) / radius, (from.1 - cy) / radius);
+    (1..=STEPS)
+        .map(|i| {
+            let theta = std::f64::consts::PI * i as f64 / STEPS as f64;
+            let (s, c) = theta.sin_cos();
+            (
+                cx + radius * (c * ux + s * bulge.0),
+                cy + radius * (c * uy + s * bulge.1),
+            )
+        })
+        .collect()
+}
+
 struct Worm {
     /// Head position and angle
     x: f64,
@@ -21,13 +129,18 @@ struct Worm {
     segments: Vec<(f64, f64)>,
     /// Color hue
     hue: f64,
-    /// Time accumulator for wobble
-    wobble_phase: f64,
-    wobble_rate: f64,
+    /// Controls steering and throttle from sensor readings; `None` for the
+    /// keyboard-controlled player worm
+    brain: Option<Network>,
+    /// Food pellets eaten this generation
+    fitness: f64,
+    /// Segment count the trail grows toward (player worms grow on eating)
+    target_length: usize,
+    is_player: bool,
 }
 
 impl Worm {
-    fn new(x: f64, y: f64, hue: f64, seed: f64) -> Self {
+    fn new(x: f64, y: f64, hue: f64, seed: f64, brain: Network) -> Self {
         let angle = seed * 6.28;
         let segments = (0..WORM_SEGMENTS)
             .map(|i| {
@@ -43,16 +156,119 @@ impl Worm {
             speed: 1.2 + seed * 1.0,
             segments,
             hue,
-            wobble_phase: seed * 6.28,
-            wobble_rate: 2.0 + seed * 3.0,
+            brain: Some(brain),
+            fitness: 0.0,
+            target_length: WORM_SEGMENTS,
+            is_player: false,
+        }
+    }
+
+    fn new_player(x: f64, y: f64, target_length: usize) -> Self {
+        let angle = 0.0;
+        let segments = (0..target_length)
+            .map(|i| (x - i as f64 * SEGMENT_SPACING, y))
+            .collect();
+        Self {
+            x,
+            y,
+            angle,
+            turn_speed: PLAYER_TURN_SPEED,
+            speed: PLAYER_SPEED,
+            segments,
+            hue: PLAYER_HUE,
+            brain: None,
+            fitness: 0.0,
+            target_length,
+            is_player: true,
+        }
+    }
+
+    /// Normalized distance/angle to the nearest food pellet and nearest
+    /// other worm head, plus own speed and a bias term.
+    fn sense(&self, foods: &[Food], neighbors: &[(f64, f64, f64)], world_diag: f64) -> [f64; 6] {
+        let (food_dist, food_angle) = foods
+            .iter()
+            .map(|f| (f.x - self.x, f.y - self.y))
+            .min_by(|a, b| {
+                (a.0 * a.0 + a.1 * a.1)
+                    .partial_cmp(&(b.0 * b.0 + b.1 * b.1))
+                    .unwrap()
+            })
+            .map(|(dx, dy)| {
+                let dist = (dx * dx + dy * dy).sqrt();
+                (
+                    (dist / world_diag).min(1.0),
+                    wrap_angle(dy.atan2(dx) - self.angle) / std::f64::consts::PI,
+                )
+            })
+            .unwrap_or((1.0, 0.0));
+
+        let (worm_dist, worm_angle) = neighbors
+            .iter()
+            .map(|&(nx, ny, _)| (nx - self.x, ny - self.y))
+            .min_by(|a, b| {
+                (a.0 * a.0 + a.1 * a.1)
+                    .partial_cmp(&(b.0 * b.0 + b.1 * b.1))
+                    .unwrap()
+            })
+            .map(|(dx, dy)| {
+                let dist = (dx * dx + dy * dy).sqrt();
+                (
+                    (dist / world_diag).min(1.0),
+                    wrap_angle(dy.atan2(dx) - self.angle) / std::f64::consts::PI,
+                )
+            })
+            .unwrap_or((1.0, 0.0));
+
+        let speed_norm = (self.speed - MIN_SPEED) / (MAX_SPEED - MIN_SPEED);
+
+        [food_dist, food_angle, worm_dist, worm_angle, speed_norm, 1.0]
+    }
+
+    fn update(
+        &mut self,
+        width: f64,
+        height: f64,
+        steering: &Steering,
+        neighbors: &[(f64, f64, f64)],
+        foods: &[Food],
+    ) {
+        let world_diag = (width * width + height * height).sqrt();
+        let sensors = self.sense(foods, neighbors, world_diag);
+        let out = self.brain.as_ref().expect("ai worm has a brain").feed_forward(&sensors);
+        let turn_signal = (out[1] - out[0]).clamp(-1.0, 1.0);
+        let speed_signal = (out[2] - out[3]).clamp(-1.0, 1.0);
+
+        self.speed = (self.speed + speed_signal * 0.05).clamp(MIN_SPEED, MAX_SPEED);
+
+        // The brain's turn decision is just another steering force, blended
+        // in alongside separation/alignment/cohesion/attractors.
+        let brain_heading = self.angle + turn_signal * std::f64::consts::FRAC_PI_4;
+        let target = steering.desired_heading(self.x, self.y, brain_heading, neighbors);
+        self.steer_toward(target);
+        self.advance(width, height);
+    }
+
+    /// Moves the player worm toward a discrete keyboard heading, ignoring
+    /// an instant 180-degree reversal into its own neck.
+    fn update_player(&mut self, width: f64, height: f64, input_heading: Option<f64>) {
+        if let Some(heading) = input_heading {
+            if wrap_angle(heading - self.angle).abs() < std::f64::consts::PI - 0.01 {
+                self.steer_toward(heading);
+            }
         }
+        self.advance(width, height);
     }
 
-    fn update(&mut self, width: f64, height: f64, time: f64) {
-        // Wobble the heading
-        self.angle += (time * self.wobble_rate + self.wobble_phase).sin() * self.turn_speed;
+    /// Turns toward `target_heading`, capped by `turn_speed` per frame.
+    fn steer_toward(&mut self, target_heading: f64) {
+        let diff = wrap_angle(target_heading - self.angle);
+        self.angle += diff.clamp(-self.turn_speed, self.turn_speed);
+    }
 
-        // Move the head
+    /// Moves the head forward, wraps it around the canvas edges, and keeps
+    /// the segment trail in sync with `target_length`.
+    fn advance(&mut self, width: f64, height: f64) {
         self.x += self.angle.cos() * self.speed;
         self.y += self.angle.sin() * self.speed;
 
@@ -73,10 +289,10 @@ impl Worm {
 
         // Update segment trail: head is first
         self.segments.insert(0, (self.x, self.y));
-        self.segments.truncate(WORM_SEGMENTS);
+        self.segments.truncate(self.target_length);
     }
 
-    fn draw(&self, ctx: &CanvasRenderingContext2d) {
+    fn draw_circles(&self, ctx: &CanvasRenderingContext2d) {
         let total = self.segments.len() as f64;
         for (i, &(sx, sy)) in self.segments.iter().enumerate() {
             let t = i as f64 / total;
@@ -94,6 +310,132 @@ impl Worm {
                 .unwrap();
             ctx.fill();
         }
+    }
+
+    /// Smooths `self.segments` with Chaikin corner-cutting, builds tapering
+    /// left/right offset rails along the smoothed centerline, and fills the
+    /// closed outline as one tapered ribbon with rounded caps.
+    fn draw_ribbon(&self, ctx: &CanvasRenderingContext2d) {
+        let centerline = chaikin_smooth(&self.segments, CHAIKIN_ITERATIONS);
+        if centerline.len() < 2 {
+            return;
+        }
+        let total = (centerline.len() - 1) as f64;
+
+        let mut left = Vec::with_capacity(centerline.len());
+        let mut right = Vec::with_capacity(centerline.len());
+        let mut unit_tangents = Vec::with_capacity(centerline.len());
+        for (i, &(px, py)) in centerline.iter().enumerate() {
+            let (tx, ty) = if i == 0 {
+                let (nx, ny) = centerline[i + 1];
+                (nx - px, ny - py)
+            } else {
+                let (pxp, pyp) = centerline[i - 1];
+                (px - pxp, py - pyp)
+            };
+            let len = (tx * tx + ty * ty).sqrt().max(1e-6);
+            let (ux, uy) = (tx / len, ty / len);
+            let (perp_x, perp_y) = (-uy, ux);
+
+            let t = i as f64 / total;
+            let radius = SEGMENT_RADIUS * (1.0 - t * 0.6);
+            left.push((px + perp_x * radius, py + perp_y * radius));
+            right.push((px - perp_x * radius, py - perp_y * radius));
+            unit_tangents.push((ux, uy));
+        }
+
+        let last = centerline.len() - 1;
+        let head_radius = SEGMENT_RADIUS;
+        let tail_radius = SEGMENT_RADIUS * (1.0 - (last as f64 / total) * 0.6);
+        // The tangent at the head points back into the body, so the head cap
+        // bulges the opposite way; the tail tangent already points outward.
+        let head_bulge = (-unit_tangents[0].0, -unit_tangents[0].1);
+        let tail_bulge = unit_tangents[last];
+
+        ctx.set_fill_style_str(&format!("hsla({}, 70%, 55%, 0.9)", self.hue));
+        ctx.begin_path();
+        ctx.move_to(left[0].0, left[0].1);
+        for &(x, y) in &left[1..] {
+            ctx.line_to(x, y);
+        }
+        for (x, y) in cap_fan(centerline[last], left[last], tail_radius, tail_bulge) {
+            ctx.line_to(x, y);
+        }
+        for &(x, y) in right[1..last].iter().rev() {
+            ctx.line_to(x, y);
+        }
+        for (x, y) in cap_fan(centerline[0], right[0], head_radius, head_bulge) {
+            ctx.line_to(x, y);
+        }
+        ctx.close_path();
+        ctx.fill();
+    }
+
+    /// Strokes the centerline once as a single round-capped, round-jointed
+    /// path, colored by a head-to-tail linear gradient, with a small radial
+    /// highlight for a glossy head.
+    fn draw_gradient_stroke(&self, ctx: &CanvasRenderingContext2d) {
+        if self.segments.len() < 2 {
+            return;
+        }
+        let (hx, hy) = self.segments[0];
+        let (tx, ty) = *self.segments.last().unwrap();
+
+        let gradient = ctx.create_linear_gradient(hx, hy, tx, ty);
+        gradient
+            .add_color_stop(0.0, &format!("hsla({}, 80%, 70%, 1.0)", self.hue))
+            .unwrap();
+        gradient
+            .add_color_stop(1.0, &format!("hsla({}, 70%, 25%, 0.5)", self.hue))
+            .unwrap();
+
+        ctx.set_stroke_style_canvas_gradient(&gradient);
+        ctx.set_line_width(SEGMENT_RADIUS * 1.6);
+        ctx.set_line_cap("round");
+        ctx.set_line_join("round");
+        ctx.begin_path();
+        ctx.move_to(hx, hy);
+        for &(x, y) in &self.segments[1..] {
+            ctx.line_to(x, y);
+        }
+        ctx.stroke();
+
+        let highlight = ctx
+            .create_radial_gradient(
+                hx - self.angle.cos() * 2.0,
+                hy - self.angle.sin() * 2.0,
+                0.0,
+                hx,
+                hy,
+                SEGMENT_RADIUS,
+            )
+            .unwrap();
+        highlight.add_color_stop(0.0, "rgba(255, 255, 255, 0.8)").unwrap();
+        highlight.add_color_stop(1.0, "rgba(255, 255, 255, 0.0)").unwrap();
+        ctx.set_fill_style_canvas_gradient(&highlight);
+        ctx.begin_path();
+        ctx.arc(hx, hy, SEGMENT_RADIUS, 0.0, std::f64::consts::TAU).unwrap();
+        ctx.fill();
+    }
+
+    fn draw(&self, ctx: &CanvasRenderingContext2d, render_mode: RenderMode) {
+        match render_mode {
+            RenderMode::Circles => self.draw_circles(ctx),
+            RenderMode::ChaikinRibbon => self.draw_ribbon(ctx),
+            RenderMode::GradientStroke => self.draw_gradient_stroke(ctx),
+        }
+
+        // Ring the player's head so it reads apart from the AI worms
+        if self.is_player {
+            if let Some(&(hx, hy)) = self.segments.first() {
+                ctx.set_stroke_style_str("white");
+                ctx.set_line_width(2.0);
+                ctx.begin_path();
+                ctx.arc(hx, hy, SEGMENT_RADIUS + 3.0, 0.0, std::f64::consts::TAU)
+                    .unwrap();
+                ctx.stroke();
+            }
+        }
 
         // Draw eyes on the head
         if let Some(&(hx, hy)) = self.segments.first() {
@@ -124,47 +466,266 @@ impl Worm {
     }
 }
 
+fn spawn_food(rng: &mut Rng, width: f64, height: f64, count: usize) -> Vec<Food> {
+    (0..count)
+        .map(|_| Food {
+            x: rng.range(0.0, width),
+            y: rng.range(0.0, height),
+        })
+        .collect()
+}
+
 struct World {
     worms: Vec<Worm>,
     width: f64,
     height: f64,
-    time: f64,
+    steering: Steering,
+    foods: Vec<Food>,
+    /// The player's own pellets, separate from `foods` so a human racing
+    /// the AI worms to pellets can't starve their fitness or force
+    /// premature generation resets.
+    player_foods: Vec<Food>,
+    rng: Rng,
+    generation: u32,
+    gen_frame: u32,
+    best_genome: Option<Network>,
+    best_fitness: f64,
+    player: Worm,
+    /// Heading requested by the most recent keydown, consumed once per tick.
+    input_heading: Option<f64>,
+    best_player_length: usize,
+    /// Frames left to show the "game over" text after a collision.
+    game_over_flash: u32,
+    /// Frames left during which the player is immune to collisions, counted
+    /// down right after a respawn.
+    respawn_grace: u32,
+    render_mode: RenderMode,
+    fire: DoomFire,
+    fire_enabled: bool,
+    attractor_preset: AttractorPreset,
 }
 
 impl World {
     fn new(width: f64, height: f64) -> Self {
+        let mut rng = Rng::new(0xC0FFEE);
         let worms = (0..NUM_WORMS)
             .map(|i| {
                 let seed = i as f64 / NUM_WORMS as f64;
                 let x = seed * width;
                 let y = (seed * 3.7 % 1.0) * height;
                 let hue = seed * 360.0;
-                Worm::new(x, y, hue, seed)
+                let brain = Network::random(&NETWORK_CONFIG, &mut rng);
+                Worm::new(x, y, hue, seed, brain)
             })
             .collect();
+        let foods = spawn_food(&mut rng, width, height, NUM_FOOD);
+        let player_foods = spawn_food(&mut rng, width, height, NUM_PLAYER_FOOD);
+        let player = Worm::new_player(width / 2.0, height / 2.0, WORM_SEGMENTS);
+        let attractor_preset = AttractorPreset::Center;
+        let mut steering = Steering::new();
+        steering.set_attractor_preset(attractor_preset, width, height);
         Self {
             worms,
             width,
             height,
-            time: 0.0,
+            steering,
+            foods,
+            player_foods,
+            rng,
+            generation: 0,
+            gen_frame: 0,
+            best_genome: None,
+            best_fitness: 0.0,
+            player,
+            input_heading: None,
+            best_player_length: WORM_SEGMENTS,
+            game_over_flash: 0,
+            respawn_grace: 0,
+            render_mode: RenderMode::Circles,
+            fire: DoomFire::new(width, height),
+            fire_enabled: false,
+            attractor_preset,
         }
     }
 
+    fn cycle_render_mode(&mut self) {
+        self.render_mode = self.render_mode.next();
+    }
+
+    fn toggle_fire(&mut self) {
+        self.fire_enabled = !self.fire_enabled;
+    }
+
+    /// Cycles through `AttractorPreset`s with `C`, so the boids' attractor
+    /// force (point pull vs. vector field) is actually visible at runtime.
+    fn cycle_attractor_preset(&mut self) {
+        self.attractor_preset = self.attractor_preset.next();
+        self.steering
+            .set_attractor_preset(self.attractor_preset, self.width, self.height);
+    }
+
     fn tick(&mut self) {
-        self.time += 1.0;
+        if self.fire_enabled {
+            self.fire.tick(&mut self.rng);
+        }
+
+        // Snapshot positions/headings before mutating so every worm steers
+        // off the same frame's neighbors.
+        let snapshot: Vec<(f64, f64, f64)> =
+            self.worms.iter().map(|w| (w.x, w.y, w.angle)).collect();
+
+        for (i, worm) in self.worms.iter_mut().enumerate() {
+            let neighbors: Vec<(f64, f64, f64)> = snapshot
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &p)| p)
+                .collect();
+            worm.update(self.width, self.height, &self.steering, &neighbors, &self.foods);
+        }
+
         for worm in &mut self.worms {
-            worm.update(self.width, self.height, self.time);
+            if let Some(idx) = self
+                .foods
+                .iter()
+                .position(|f| (f.x - worm.x).hypot(f.y - worm.y) <= SEGMENT_RADIUS)
+            {
+                self.foods.remove(idx);
+                worm.fitness += 1.0;
+            }
+        }
+
+        self.gen_frame += 1;
+        if self.foods.is_empty() || self.gen_frame >= FRAME_BUDGET {
+            self.next_generation();
+        }
+
+        self.tick_player();
+    }
+
+    fn tick_player(&mut self) {
+        self.player
+            .update_player(self.width, self.height, self.input_heading.take());
+
+        if let Some(idx) = self
+            .player_foods
+            .iter()
+            .position(|f| (f.x - self.player.x).hypot(f.y - self.player.y) <= SEGMENT_RADIUS)
+        {
+            self.player_foods.remove(idx);
+            self.player_foods.push(Food {
+                x: self.rng.range(0.0, self.width),
+                y: self.rng.range(0.0, self.height),
+            });
+            self.player.target_length += 1;
+        }
+
+        if self.respawn_grace > 0 {
+            self.respawn_grace -= 1;
+        }
+
+        let hit_self = self.respawn_grace == 0
+            && self
+                .player
+                .segments
+                .iter()
+                .skip(NECK_GAP)
+                .any(|&(sx, sy)| (sx - self.player.x).hypot(sy - self.player.y) <= SEGMENT_RADIUS);
+        let hit_other = self.respawn_grace == 0
+            && self.worms.iter().any(|w| {
+                w.segments
+                    .iter()
+                    .any(|&(sx, sy)| (sx - self.player.x).hypot(sy - self.player.y) <= SEGMENT_RADIUS)
+            });
+
+        if hit_self || hit_other {
+            self.best_player_length = self.best_player_length.max(self.player.target_length);
+            self.player = Worm::new_player(self.width / 2.0, self.height / 2.0, self.best_player_length);
+            self.game_over_flash = GAME_OVER_FLASH_FRAMES;
+            self.respawn_grace = RESPAWN_GRACE_FRAMES;
+        } else if self.game_over_flash > 0 {
+            self.game_over_flash -= 1;
         }
     }
 
+    /// Breeds the next population from the fittest worms: the global best
+    /// genome survives unmutated, the rest are bred from the current top
+    /// performers with Gaussian-perturbed weights.
+    fn next_generation(&mut self) {
+        if let Some(best) = self
+            .worms
+            .iter()
+            .max_by(|a, b| a.fitness.partial_cmp(&b.fitness).unwrap())
+        {
+            if best.fitness > self.best_fitness || self.best_genome.is_none() {
+                self.best_fitness = best.fitness;
+                self.best_genome = Some(best.brain.as_ref().unwrap().clone_unmutated());
+            }
+        }
+
+        let mut order: Vec<usize> = (0..self.worms.len()).collect();
+        order.sort_by(|&a, &b| {
+            self.worms[b]
+                .fitness
+                .partial_cmp(&self.worms[a].fitness)
+                .unwrap()
+        });
+        let elite = ELITE_COUNT.min(order.len());
+        let parents: Vec<Network> = order[..elite]
+            .iter()
+            .map(|&i| self.worms[i].brain.as_ref().unwrap().clone_unmutated())
+            .collect();
+        let best_genome = self.best_genome.as_ref().unwrap().clone_unmutated();
+
+        self.worms = (0..NUM_WORMS)
+            .map(|i| {
+                let seed = i as f64 / NUM_WORMS as f64;
+                let x = seed * self.width;
+                let y = (seed * 3.7 % 1.0) * self.height;
+                let hue = seed * 360.0;
+                let brain = if i == 0 {
+                    best_genome.clone_unmutated()
+                } else {
+                    parents[i % elite].breed(&mut self.rng, MUT_RATE)
+                };
+                Worm::new(x, y, hue, seed, brain)
+            })
+            .collect();
+
+        self.foods = spawn_food(&mut self.rng, self.width, self.height, NUM_FOOD);
+        self.generation += 1;
+        self.gen_frame = 0;
+
+        console::log_1(
+            &format!(
+                "generation {} — best fitness so far: {}",
+                self.generation, self.best_fitness
+            )
+            .into(),
+        );
+    }
+
     fn draw(&self, ctx: &CanvasRenderingContext2d) {
-        // Semi-transparent clear for motion trails
+        if self.fire_enabled {
+            self.fire.draw(ctx);
+        }
+
+        // Semi-transparent clear for motion trails; over the fire this also
+        // reads as a glowing floor the trails fade into.
         ctx.set_fill_style_str("rgba(30, 20, 40, 0.25)");
         ctx.fill_rect(0.0, 0.0, self.width, self.height);
 
+        ctx.set_fill_style_str("rgba(255, 220, 120, 0.9)");
+        for food in self.foods.iter().chain(&self.player_foods) {
+            ctx.begin_path();
+            ctx.arc(food.x, food.y, 3.0, 0.0, std::f64::consts::TAU).unwrap();
+            ctx.fill();
+        }
+
         for worm in &self.worms {
-            worm.draw(ctx);
+            worm.draw(ctx, self.render_mode);
         }
+        self.player.draw(ctx, self.render_mode);
 
         // Draw title text
         ctx.set_font("bold 48px monospace");
@@ -179,9 +740,32 @@ impl World {
 
         // Reset shadow
         ctx.set_shadow_blur(0.0);
+
+        ctx.set_font("20px monospace");
+        ctx.set_fill_style_str("#f0e0ff");
+        ctx.fill_text(&format!("length: {}", self.player.target_length), self.width / 2.0, 90.0)
+            .unwrap();
+
+        if self.game_over_flash > 0 {
+            ctx.set_font("bold 36px monospace");
+            ctx.set_fill_style_str("#ff5050");
+            ctx.fill_text("game over", self.width / 2.0, self.height / 2.0)
+                .unwrap();
+        }
     }
 }
 
+/// Wraps an angle into `(-PI, PI]`.
+fn wrap_angle(angle: f64) -> f64 {
+    let mut a = angle % std::f64::consts::TAU;
+    if a > std::f64::consts::PI {
+        a -= std::f64::consts::TAU;
+    } else if a <= -std::f64::consts::PI {
+        a += std::f64::consts::TAU;
+    }
+    a
+}
+
 fn request_animation_frame(f: &Closure<dyn FnMut()>) {
     web_sys::window()
         .unwrap()
@@ -217,6 +801,35 @@ pub fn main() {
 
     let world = Rc::new(RefCell::new(World::new(width, height)));
 
+    // WASD/arrow keys set the player's desired heading; the next tick turns
+    // toward it, smoothly, rather than snapping to a grid.
+    let keys = world.clone();
+    let keydown = Closure::wrap(Box::new(move |event: KeyboardEvent| {
+        let heading = match event.key().as_str() {
+            "ArrowUp" | "w" | "W" => Some(-std::f64::consts::FRAC_PI_2),
+            "ArrowDown" | "s" | "S" => Some(std::f64::consts::FRAC_PI_2),
+            "ArrowLeft" | "a" | "A" => Some(std::f64::consts::PI),
+            "ArrowRight" | "d" | "D" => Some(0.0),
+            _ => None,
+        };
+        if let Some(heading) = heading {
+            keys.borrow_mut().input_heading = Some(heading);
+        }
+        if matches!(event.key().as_str(), "r" | "R") {
+            keys.borrow_mut().cycle_render_mode();
+        }
+        if matches!(event.key().as_str(), "f" | "F") {
+            keys.borrow_mut().toggle_fire();
+        }
+        if matches!(event.key().as_str(), "c" | "C") {
+            keys.borrow_mut().cycle_attractor_preset();
+        }
+    }) as Box<dyn FnMut(KeyboardEvent)>);
+    window
+        .add_event_listener_with_callback("keydown", keydown.as_ref().unchecked_ref())
+        .unwrap();
+    keydown.forget();
+
     // Animation loop
     let f: Rc<RefCell<Option<Closure<dyn FnMut()>>>> = Rc::new(RefCell::new(None));
     let g = f.clone();
@@ -0,0 +1,106 @@
+//! Tiny feed-forward network and genetic-algorithm plumbing for worm brains,
+//! modeled on the asteroids-genetic design: dense layers, ReLU activation,
+//! and Gaussian-perturbed breeding.
+
+use crate::rng::Rng;
+
+struct Layer {
+    weights: Vec<Vec<f64>>,
+    biases: Vec<f64>,
+}
+
+impl Layer {
+    fn random(inputs: usize, outputs: usize, rng: &mut Rng) -> Self {
+        let weights = (0..outputs)
+            .map(|_| (0..inputs).map(|_| rng.range(-1.0, 1.0)).collect())
+            .collect();
+        let biases = (0..outputs).map(|_| rng.range(-1.0, 1.0)).collect();
+        Self { weights, biases }
+    }
+
+    fn feed_forward(&self, inputs: &[f64]) -> Vec<f64> {
+        self.weights
+            .iter()
+            .zip(&self.biases)
+            .map(|(row, bias)| {
+                let sum: f64 = row.iter().zip(inputs).map(|(w, i)| w * i).sum();
+                (sum + bias).max(0.0) // ReLU
+            })
+            .collect()
+    }
+}
+
+/// A small feed-forward network controlling one worm, e.g. `[6, 7, 7, 4]`
+/// for 6 sensor inputs, two hidden layers of 7, and 4 steering outputs.
+pub struct Network {
+    layers: Vec<Layer>,
+}
+
+impl Network {
+    pub fn random(config: &[usize], rng: &mut Rng) -> Self {
+        let layers = config
+            .windows(2)
+            .map(|pair| Layer::random(pair[0], pair[1], rng))
+            .collect();
+        Self { layers }
+    }
+
+    pub fn feed_forward(&self, inputs: &[f64]) -> Vec<f64> {
+        let mut activations = inputs.to_vec();
+        for layer in &self.layers {
+            activations = layer.feed_forward(&activations);
+        }
+        activations
+    }
+
+    /// Clones `self`, perturbing each weight and bias with probability
+    /// `mut_rate` by adding small Gaussian noise.
+    pub fn breed(&self, rng: &mut Rng, mut_rate: f64) -> Network {
+        let layers = self
+            .layers
+            .iter()
+            .map(|layer| {
+                let weights = layer
+                    .weights
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|&w| {
+                                if rng.next_f64() < mut_rate {
+                                    w + rng.gaussian() * 0.2
+                                } else {
+                                    w
+                                }
+                            })
+                            .collect()
+                    })
+                    .collect();
+                let biases = layer
+                    .biases
+                    .iter()
+                    .map(|&b| {
+                        if rng.next_f64() < mut_rate {
+                            b + rng.gaussian() * 0.2
+                        } else {
+                            b
+                        }
+                    })
+                    .collect();
+                Layer { weights, biases }
+            })
+            .collect();
+        Network { layers }
+    }
+
+    pub fn clone_unmutated(&self) -> Network {
+        let layers = self
+            .layers
+            .iter()
+            .map(|l| Layer {
+                weights: l.weights.clone(),
+                biases: l.biases.clone(),
+            })
+            .collect();
+        Network { layers }
+    }
+}
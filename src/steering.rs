@@ -0,0 +1,176 @@
+//! Boids-style steering: separation, alignment, cohesion and configurable
+//! attractor forces, blended into a single desired heading per worm.
+
+use crate::SEGMENT_RADIUS;
+
+/// A force pulling worms toward a point or field.
+pub enum Attractor {
+    /// Pulls toward a fixed point in world space.
+    Point { x: f64, y: f64, strength: f64 },
+    /// A de-Jong/Clifford-style vector field: force at `(x, y)` is
+    /// `(sin(a*y) - cos(b*x), sin(c*x) - cos(d*y))`.
+    Field {
+        a: f64,
+        b: f64,
+        c: f64,
+        d: f64,
+        strength: f64,
+    },
+}
+
+/// Relative weight of each steering force in the final blend.
+pub struct SteeringWeights {
+    pub separation: f64,
+    pub alignment: f64,
+    pub cohesion: f64,
+    pub attractors: f64,
+    pub wobble: f64,
+}
+
+impl Default for SteeringWeights {
+    fn default() -> Self {
+        Self {
+            separation: 1.5,
+            alignment: 0.6,
+            cohesion: 0.4,
+            attractors: 1.0,
+            wobble: 0.8,
+        }
+    }
+}
+
+/// Named attractor setups a user can cycle through at runtime with `C`.
+#[derive(Clone, Copy, PartialEq)]
+pub enum AttractorPreset {
+    /// No attractor force; pure flocking.
+    None,
+    /// A single point attractor at the center of the canvas.
+    Center,
+    /// A de-Jong/Clifford-style vector field covering the canvas.
+    Field,
+}
+
+impl AttractorPreset {
+    pub fn next(self) -> Self {
+        match self {
+            AttractorPreset::None => AttractorPreset::Center,
+            AttractorPreset::Center => AttractorPreset::Field,
+            AttractorPreset::Field => AttractorPreset::None,
+        }
+    }
+}
+
+/// Tunable boids behavior shared by all worms in a `World`.
+pub struct Steering {
+    pub weights: SteeringWeights,
+    pub attractors: Vec<Attractor>,
+    /// How far a worm looks for neighbors when flocking.
+    pub neighbor_radius: f64,
+    /// Separation kicks in for neighbors closer than `SEGMENT_RADIUS * k`.
+    pub separation_k: f64,
+}
+
+impl Default for Steering {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Steering {
+    pub fn new() -> Self {
+        Self {
+            weights: SteeringWeights::default(),
+            attractors: Vec::new(),
+            neighbor_radius: 80.0,
+            separation_k: 4.0,
+        }
+    }
+
+    /// Replaces `self.attractors` with the named preset, sized to the canvas.
+    pub fn set_attractor_preset(&mut self, preset: AttractorPreset, width: f64, height: f64) {
+        self.attractors = match preset {
+            AttractorPreset::None => Vec::new(),
+            AttractorPreset::Center => vec![Attractor::Point {
+                x: width / 2.0,
+                y: height / 2.0,
+                strength: 0.02,
+            }],
+            AttractorPreset::Field => vec![Attractor::Field {
+                a: 2.0 / width,
+                b: 2.0 / height,
+                c: 2.0 / width,
+                d: 2.0 / height,
+                strength: 0.8,
+            }],
+        };
+    }
+
+    /// Blends flocking and attractor forces with the supplied wobble heading
+    /// and returns the resulting target heading in radians.
+    pub fn desired_heading(
+        &self,
+        x: f64,
+        y: f64,
+        wobble_heading: f64,
+        neighbors: &[(f64, f64, f64)],
+    ) -> f64 {
+        let separation_radius = SEGMENT_RADIUS * self.separation_k;
+        let mut separation = (0.0, 0.0);
+        let mut alignment = (0.0, 0.0);
+        let mut centroid = (0.0, 0.0);
+        let mut flock_count = 0.0;
+
+        for &(nx, ny, nangle) in neighbors {
+            let dx = x - nx;
+            let dy = y - ny;
+            let dist = (dx * dx + dy * dy).sqrt();
+            if dist <= 0.0 || dist > self.neighbor_radius {
+                continue;
+            }
+            if dist < separation_radius {
+                separation.0 += dx / dist;
+                separation.1 += dy / dist;
+            }
+            alignment.0 += nangle.cos();
+            alignment.1 += nangle.sin();
+            centroid.0 += nx;
+            centroid.1 += ny;
+            flock_count += 1.0;
+        }
+
+        let mut fx = separation.0 * self.weights.separation;
+        let mut fy = separation.1 * self.weights.separation;
+
+        if flock_count > 0.0 {
+            fx += alignment.0 * self.weights.alignment;
+            fy += alignment.1 * self.weights.alignment;
+
+            let cohesion = (
+                centroid.0 / flock_count - x,
+                centroid.1 / flock_count - y,
+            );
+            fx += cohesion.0 * self.weights.cohesion;
+            fy += cohesion.1 * self.weights.cohesion;
+        }
+
+        for attractor in &self.attractors {
+            let (ax, ay, strength) = match *attractor {
+                Attractor::Point { x: tx, y: ty, strength } => (tx - x, ty - y, strength),
+                Attractor::Field { a, b, c, d, strength } => {
+                    ((a * y).sin() - (b * x).cos(), (c * x).sin() - (d * y).cos(), strength)
+                }
+            };
+            fx += ax * strength * self.weights.attractors;
+            fy += ay * strength * self.weights.attractors;
+        }
+
+        fx += wobble_heading.cos() * self.weights.wobble;
+        fy += wobble_heading.sin() * self.weights.wobble;
+
+        if fx == 0.0 && fy == 0.0 {
+            wobble_heading
+        } else {
+            fy.atan2(fx)
+        }
+    }
+}